@@ -0,0 +1,121 @@
+use embassy_time::Duration;
+
+/// A runtime-swappable LED blink timing pattern, driven by [`crate::scheduler::BlinkScheduler`].
+#[derive(Debug, Clone)]
+pub enum BlinkPattern {
+    /// A fixed on-duration / off-duration duty cycle.
+    Steady { on: Duration, off: Duration },
+    /// A sequence of durations, alternating on/off state starting with "on".
+    /// Useful for morse-like patterns.
+    Sequence(Vec<Duration>),
+}
+
+impl BlinkPattern {
+    /// A simple on/off duty cycle, given in milliseconds.
+    pub fn steady(on_ms: u64, off_ms: u64) -> Self {
+        Self::Steady {
+            on: Duration::from_millis(on_ms),
+            off: Duration::from_millis(off_ms),
+        }
+    }
+
+    /// A sequence of durations (in milliseconds), alternating on/off state
+    /// starting with "on". Panics if `durations_ms` is empty.
+    pub fn sequence(durations_ms: impl IntoIterator<Item = u64>) -> Self {
+        let durations: Vec<Duration> = durations_ms.into_iter().map(Duration::from_millis).collect();
+        assert!(
+            !durations.is_empty(),
+            "BlinkPattern::sequence requires at least one duration"
+        );
+        Self::Sequence(durations)
+    }
+
+    /// Parse a pattern spec of the form `steady:<on_ms>,<off_ms>` or
+    /// `sequence:<ms>,<ms>,...`, as sent over the pattern command topic.
+    /// Returns `None` if the spec is malformed or the sequence is empty.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (kind, rest) = spec.split_once(':')?;
+        let values: Vec<u64> = rest
+            .split(',')
+            .map(|v| v.trim().parse().ok())
+            .collect::<Option<_>>()?;
+
+        match kind {
+            "steady" => {
+                let [on, off]: [u64; 2] = values.try_into().ok()?;
+                Some(Self::steady(on, off))
+            }
+            "sequence" if !values.is_empty() => Some(Self::sequence(values)),
+            _ => None,
+        }
+    }
+
+    /// The duration to hold and the LED state ("on") for the given step index.
+    pub fn step(&self, index: usize) -> (Duration, bool) {
+        match self {
+            Self::Steady { on, off } => {
+                if index % 2 == 0 {
+                    (*on, true)
+                } else {
+                    (*off, false)
+                }
+            }
+            Self::Sequence(durations) => {
+                let position = index % durations.len();
+                (durations[position], position % 2 == 0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steady_alternates_on_and_off_durations() {
+        let pattern = BlinkPattern::steady(500, 800);
+        assert_eq!(pattern.step(0), (Duration::from_millis(500), true));
+        assert_eq!(pattern.step(1), (Duration::from_millis(800), false));
+        assert_eq!(pattern.step(2), (Duration::from_millis(500), true));
+    }
+
+    #[test]
+    fn sequence_wraps_and_alternates_state() {
+        let pattern = BlinkPattern::sequence([100, 200, 300]);
+        assert_eq!(pattern.step(0), (Duration::from_millis(100), true));
+        assert_eq!(pattern.step(1), (Duration::from_millis(200), false));
+        assert_eq!(pattern.step(2), (Duration::from_millis(300), true));
+        // Wraps back to the first element after the sequence ends, keeping
+        // the same state it had the first time through (position-based, not
+        // raw-index-based, so odd-length sequences don't flip polarity).
+        assert_eq!(pattern.step(3), (Duration::from_millis(100), true));
+    }
+
+    #[test]
+    #[should_panic(expected = "requires at least one duration")]
+    fn sequence_panics_on_empty_input() {
+        BlinkPattern::sequence([]);
+    }
+
+    #[test]
+    fn parse_steady_spec() {
+        let pattern = BlinkPattern::parse("steady:500,800").unwrap();
+        assert_eq!(pattern.step(0), (Duration::from_millis(500), true));
+        assert_eq!(pattern.step(1), (Duration::from_millis(800), false));
+    }
+
+    #[test]
+    fn parse_sequence_spec() {
+        let pattern = BlinkPattern::parse("sequence:100,200,300").unwrap();
+        assert_eq!(pattern.step(1), (Duration::from_millis(200), false));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_specs() {
+        assert!(BlinkPattern::parse("steady:500").is_none());
+        assert!(BlinkPattern::parse("sequence:").is_none());
+        assert!(BlinkPattern::parse("bogus:1,2").is_none());
+        assert!(BlinkPattern::parse("no-colon").is_none());
+    }
+}
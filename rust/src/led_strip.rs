@@ -0,0 +1,125 @@
+use esp_idf_hal::gpio::OutputPin;
+use esp_idf_hal::peripheral::Peripheral;
+use esp_idf_hal::rmt::{
+    config::TransmitConfig, PinState, Pulse, RmtChannel, TxRmtDriver, VariableLengthSignal,
+};
+use esp_idf_svc::sys::EspError;
+use std::time::Duration;
+
+/// An RGB pixel for a WS2812-style addressable LED.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// An RGBW pixel for an SK6812-style addressable LED, with a dedicated white channel.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rgbw {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub w: u8,
+}
+
+impl Rgbw {
+    pub const fn new(r: u8, g: u8, b: u8, w: u8) -> Self {
+        Self { r, g, b, w }
+    }
+}
+
+/// An RMT-based driver for WS2812/SK6812 addressable LED strips.
+///
+/// Encodes each color bit as the high/low pulse pair the WS2812 800kHz
+/// protocol expects and transmits a whole frame in a single RMT transaction.
+pub struct LedStrip<'d> {
+    tx: TxRmtDriver<'d>,
+    t0h: Pulse,
+    t0l: Pulse,
+    t1h: Pulse,
+    t1l: Pulse,
+    reset: Pulse,
+}
+
+impl<'d> LedStrip<'d> {
+    /// Create a driver over the given RMT channel and data pin.
+    pub fn new(
+        channel: impl Peripheral<P = impl RmtChannel> + 'd,
+        pin: impl Peripheral<P = impl OutputPin> + 'd,
+    ) -> Result<Self, EspError> {
+        let config = TransmitConfig::new().clock_divider(1);
+        let tx = TxRmtDriver::new(channel, pin, &config)?;
+
+        // Derive pulse widths from the channel's actual tick rate rather than
+        // assuming one, since it depends on the source clock / divider.
+        let ticks_hz = tx.counter_clock()?;
+
+        // 0 bit: ~0.4us high / ~0.85us low
+        let t0h = Pulse::new_with_duration(ticks_hz, PinState::High, &Duration::from_nanos(400))?;
+        let t0l = Pulse::new_with_duration(ticks_hz, PinState::Low, &Duration::from_nanos(850))?;
+        // 1 bit: ~0.8us high / ~0.45us low
+        let t1h = Pulse::new_with_duration(ticks_hz, PinState::High, &Duration::from_nanos(800))?;
+        let t1l = Pulse::new_with_duration(ticks_hz, PinState::Low, &Duration::from_nanos(450))?;
+        // Reset/latch: hold the line low for >50us.
+        let reset = Pulse::new_with_duration(ticks_hz, PinState::Low, &Duration::from_micros(60))?;
+
+        Ok(Self {
+            tx,
+            t0h,
+            t0l,
+            t1h,
+            t1l,
+            reset,
+        })
+    }
+
+    /// Encode and transmit a frame of RGB pixels (GRB wire order, as WS2812 expects)
+    /// in a single RMT transaction.
+    pub fn write_pixels(&mut self, pixels: &[Rgb]) -> Result<(), EspError> {
+        let mut signal = VariableLengthSignal::with_capacity(pixels.len() * 24 + 1);
+        for pixel in pixels {
+            self.encode_bytes(&mut signal, [pixel.g, pixel.r, pixel.b])?;
+        }
+        self.finish(signal)
+    }
+
+    /// Encode and transmit a frame of RGBW pixels (GRBW wire order, as SK6812
+    /// expects) in a single RMT transaction.
+    pub fn write_pixels_rgbw(&mut self, pixels: &[Rgbw]) -> Result<(), EspError> {
+        let mut signal = VariableLengthSignal::with_capacity(pixels.len() * 32 + 1);
+        for pixel in pixels {
+            self.encode_bytes(&mut signal, [pixel.g, pixel.r, pixel.b, pixel.w])?;
+        }
+        self.finish(signal)
+    }
+
+    fn encode_bytes<const N: usize>(
+        &self,
+        signal: &mut VariableLengthSignal,
+        bytes: [u8; N],
+    ) -> Result<(), EspError> {
+        for byte in bytes {
+            for i in (0..8).rev() {
+                let bit = (byte >> i) & 1 == 1;
+                if bit {
+                    signal.push([&self.t1h, &self.t1l])?;
+                } else {
+                    signal.push([&self.t0h, &self.t0l])?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, mut signal: VariableLengthSignal) -> Result<(), EspError> {
+        signal.push([&self.reset, &Pulse::zero()])?;
+        self.tx.start_blocking(&signal)
+    }
+}
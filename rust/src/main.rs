@@ -1,40 +1,56 @@
-use esp_idf_hal::gpio::PinDriver;
-use esp_idf_hal::delay::FreeRtos;
+mod blink_pattern;
+mod led_strip;
+mod net;
+mod scheduler;
+mod tasks;
+mod watchdog;
+
 use esp_idf_hal::peripherals::Peripherals;
+use esp_idf_svc::eventloop::EspSystemEventLoop;
+use esp_idf_svc::nvs::EspDefaultNvsPartition;
+use led_strip::{LedStrip, Rgbw};
+use static_cell::StaticCell;
+
+static EXECUTOR: StaticCell<embassy_executor::Executor> = StaticCell::new();
 
 fn main() {
     // Initialize ESP-IDF
     esp_idf_svc::sys::link_patches();
     esp_idf_svc::log::EspLogger::initialize_default();
 
-    // Configure watchdog with longer timeout or disable it
-    unsafe {
-        let config = esp_idf_svc::sys::esp_task_wdt_config_t {
-            timeout_ms: 10000,        // 10 second timeout
-            idle_core_mask: (1 << 0), // Monitor core 0's idle task
-            trigger_panic: true,      // Trigger panic on timeout
-        };
-        let result = esp_idf_svc::sys::esp_task_wdt_init(&config);
-        if result != 0 {
-            log::warn!("Failed to initialize watchdog timer: {}", result);
-        }
+    if let Err(e) = watchdog::enable(watchdog::TIMEOUT_MS) {
+        log::warn!("Failed to initialize watchdog timer: {:?}", e);
     }
 
-
     let peripherals = Peripherals::take().unwrap();
-    let mut led = PinDriver::output(peripherals.pins.gpio8).unwrap();
+    let mut led = LedStrip::new(peripherals.rmt.channel0, peripherals.pins.gpio8).unwrap();
 
-    log::info!("Flashing LED on pin 8");
-    loop {
-        if let Err(e) = led.set_high() {
-            log::error!("Failed to set LED high: {:?}", e);
+    // Boot indicator on the dedicated white channel, for SK6812 strips.
+    if let Err(e) = led.write_pixels_rgbw(&[Rgbw::new(0, 0, 0, 20)]) {
+        log::warn!("Failed to write SK6812 boot indicator: {:?}", e);
+    }
+
+    let sys_loop = EspSystemEventLoop::take().unwrap();
+    let nvs = EspDefaultNvsPartition::take().unwrap();
+    let mqtt = match net::connect_wifi(peripherals.modem, sys_loop, nvs) {
+        Ok(wifi) => {
+            // Keep the WiFi driver alive for the lifetime of the program.
+            Box::leak(Box::new(wifi));
+            net::Mqtt::new()
+                .inspect_err(|e| log::error!("Failed to connect MQTT client: {:?}", e))
+                .ok()
         }
-        FreeRtos::delay_ms(500); // Use FreeRtos delay which handles watchdog timers
-        
-        if let Err(e) = led.set_low() {
-            log::error!("Failed to set LED low: {:?}", e);
+        Err(e) => {
+            log::error!("Failed to connect WiFi, running offline: {:?}", e);
+            None
         }
-        log::info!("Hehhhhhhhhhhllo, world!");
-        FreeRtos::delay_ms(800); // Use FreeRtos delay which handles watchdog timers
-    }
-}
\ No newline at end of file
+    };
+
+    log::info!("Starting embassy executor");
+    let executor = EXECUTOR.init(embassy_executor::Executor::new());
+    executor.run(|spawner| {
+        // Additional tasks (sensors, ...) can be spawned here without
+        // restructuring the executor setup.
+        spawner.spawn(tasks::blink(led, mqtt)).unwrap();
+    });
+}
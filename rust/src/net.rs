@@ -0,0 +1,198 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use embassy_time::Duration;
+use embedded_svc::mqtt::client::{EventPayload, QoS};
+use embedded_svc::wifi::{AuthMethod, ClientConfiguration, Configuration};
+use esp_idf_hal::delay::FreeRtos;
+use esp_idf_hal::modem::Modem;
+use esp_idf_hal::peripheral::Peripheral;
+use esp_idf_svc::eventloop::EspSystemEventLoop;
+use esp_idf_svc::mqtt::client::{EspMqttClient, MqttClientConfiguration};
+use esp_idf_svc::nvs::EspDefaultNvsPartition;
+use esp_idf_svc::sys::EspError;
+use esp_idf_svc::wifi::EspWifi;
+
+use crate::watchdog;
+
+/// WiFi network to join. Replace with your own before flashing.
+pub const WIFI_SSID: &str = "CHANGE_ME";
+/// WiFi password for [`WIFI_SSID`].
+pub const WIFI_PASSWORD: &str = "CHANGE_ME";
+
+/// Broker URL, e.g. `mqtt://broker.local:1883`.
+pub const MQTT_URL: &str = "mqtt://broker.local:1883";
+/// Client id announced to the broker.
+pub const MQTT_CLIENT_ID: &str = "esp-blinker";
+/// Topic telemetry is published to.
+pub const TELEMETRY_TOPIC: &str = "esp-blinker/telemetry";
+/// Topic this device listens on to toggle the LED remotely.
+pub const COMMAND_TOPIC: &str = "esp-blinker/command";
+/// Topic this device listens on to select a [`crate::blink_pattern::BlinkPattern`]
+/// remotely, as a `steady:<on_ms>,<off_ms>` or `sequence:<ms>,<ms>,...` spec.
+pub const PATTERN_TOPIC: &str = "esp-blinker/pattern";
+
+/// How often the WiFi connect loop feeds the watchdog while polling for an
+/// association + DHCP lease, which routinely takes longer than the watchdog
+/// timeout on a real network.
+const CONNECT_POLL_MS: u32 = 250;
+
+/// Bring up the WiFi station interface and poll until an IP is assigned.
+///
+/// Polls rather than blocking on a single call so the watchdog (already
+/// subscribed for the calling task by the time this runs) keeps getting fed
+/// throughout association and DHCP negotiation.
+pub fn connect_wifi<'d>(
+    modem: impl Peripheral<P = Modem> + 'd,
+    sys_loop: EspSystemEventLoop,
+    nvs: EspDefaultNvsPartition,
+) -> Result<EspWifi<'d>, EspError> {
+    let mut wifi = EspWifi::new(modem, sys_loop, Some(nvs))?;
+
+    wifi.set_configuration(&Configuration::Client(ClientConfiguration {
+        ssid: WIFI_SSID.try_into().unwrap(),
+        password: WIFI_PASSWORD.try_into().unwrap(),
+        auth_method: AuthMethod::WPA2Personal,
+        ..Default::default()
+    }))?;
+
+    wifi.start()?;
+    log::info!("Connecting to WiFi SSID '{}'", WIFI_SSID);
+    wifi.connect()?;
+
+    while !(wifi.is_connected()? && wifi.sta_netif().is_up()?) {
+        if let Err(e) = watchdog::feed() {
+            log::warn!("Failed to feed watchdog while connecting WiFi: {:?}", e);
+        }
+        FreeRtos::delay_ms(CONNECT_POLL_MS);
+    }
+
+    log::info!("WiFi connected, IP: {:?}", wifi.sta_netif().get_ip_info()?);
+
+    Ok(wifi)
+}
+
+/// JSON telemetry payload published on [`TELEMETRY_TOPIC`].
+#[derive(Debug, Clone, Copy)]
+pub struct Telemetry {
+    pub uptime_secs: u64,
+    pub heap_free: usize,
+    pub led_on: bool,
+}
+
+impl Telemetry {
+    fn to_json(self) -> String {
+        format!(
+            r#"{{"uptime_secs":{},"heap_free":{},"led_on":{}}}"#,
+            self.uptime_secs, self.heap_free, self.led_on
+        )
+    }
+}
+
+/// A connected MQTT client that publishes telemetry and accepts LED and
+/// blink-pattern commands.
+///
+/// `command_led_on`/`override_active` and `requested_pattern` are written by
+/// the subscription callback thread; the blink task reads them to drive the
+/// LED. Receiving a command on [`COMMAND_TOPIC`] forces the LED to that state
+/// until a new pattern is selected on [`PATTERN_TOPIC`], which hands control
+/// back to the local [`crate::scheduler::BlinkScheduler`].
+pub struct Mqtt {
+    client: EspMqttClient<'static>,
+    command_led_on: Arc<AtomicBool>,
+    override_active: Arc<AtomicBool>,
+    requested_pattern: Arc<Mutex<Option<String>>>,
+}
+
+impl Mqtt {
+    /// Connect to [`MQTT_URL`] and subscribe to [`COMMAND_TOPIC`] and [`PATTERN_TOPIC`].
+    pub fn new() -> Result<Self, EspError> {
+        let command_led_on = Arc::new(AtomicBool::new(false));
+        let override_active = Arc::new(AtomicBool::new(false));
+        let requested_pattern = Arc::new(Mutex::new(None));
+
+        let callback_led_on = command_led_on.clone();
+        let callback_override_active = override_active.clone();
+        let callback_pattern = requested_pattern.clone();
+
+        let (mut client, mut connection) = EspMqttClient::new(
+            MQTT_URL,
+            &MqttClientConfiguration {
+                client_id: Some(MQTT_CLIENT_ID),
+                ..Default::default()
+            },
+        )?;
+
+        std::thread::spawn(move || {
+            while let Ok(event) = connection.next() {
+                let EventPayload::Received { topic: Some(topic), data, .. } = event.payload()
+                else {
+                    continue;
+                };
+
+                match topic {
+                    COMMAND_TOPIC => {
+                        let on = matches!(data, b"1" | b"on" | b"ON" | b"true");
+                        callback_led_on.store(on, Ordering::Relaxed);
+                        callback_override_active.store(true, Ordering::Relaxed);
+                    }
+                    PATTERN_TOPIC => {
+                        *callback_pattern.lock().unwrap() =
+                            Some(String::from_utf8_lossy(data).into_owned());
+                        // A new pattern hands control back to the scheduler.
+                        callback_override_active.store(false, Ordering::Relaxed);
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        client.subscribe(COMMAND_TOPIC, QoS::AtLeastOnce)?;
+        client.subscribe(PATTERN_TOPIC, QoS::AtLeastOnce)?;
+
+        Ok(Self {
+            client,
+            command_led_on,
+            override_active,
+            requested_pattern,
+        })
+    }
+
+    /// Publish a telemetry snapshot to [`TELEMETRY_TOPIC`].
+    pub fn publish_telemetry(&mut self, telemetry: Telemetry) -> Result<(), EspError> {
+        self.client.publish(
+            TELEMETRY_TOPIC,
+            QoS::AtLeastOnce,
+            false,
+            telemetry.to_json().as_bytes(),
+        )
+    }
+
+    /// The LED state forced by [`COMMAND_TOPIC`], if a command is in effect.
+    /// Returns `None` once a pattern has been selected on [`PATTERN_TOPIC`],
+    /// letting the caller fall back to its local schedule.
+    pub fn requested_led_state(&self) -> Option<bool> {
+        self.override_active
+            .load(Ordering::Relaxed)
+            .then(|| self.command_led_on.load(Ordering::Relaxed))
+    }
+
+    /// Take the most recently received [`PATTERN_TOPIC`] spec, if any, so it
+    /// is only applied once.
+    pub fn take_requested_pattern(&self) -> Option<String> {
+        self.requested_pattern.lock().unwrap().take()
+    }
+}
+
+/// Approximate seconds since boot, for telemetry purposes.
+pub fn uptime_secs() -> u64 {
+    unsafe { esp_idf_svc::sys::esp_timer_get_time() as u64 / 1_000_000 }
+}
+
+/// Free heap size in bytes, for telemetry purposes.
+pub fn heap_free() -> usize {
+    unsafe { esp_idf_svc::sys::esp_get_free_heap_size() as usize }
+}
+
+/// How often telemetry is published when there is no state change to report.
+pub const PUBLISH_INTERVAL: Duration = Duration::from_secs(10);
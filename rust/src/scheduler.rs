@@ -0,0 +1,105 @@
+use embassy_time::Instant;
+
+use crate::blink_pattern::BlinkPattern;
+
+/// Tracks the next toggle deadline for a [`BlinkPattern`] against a monotonic
+/// tick source, without blocking. Callers poll it on every pass of a
+/// cooperative loop alongside other work (e.g. feeding the watchdog), instead
+/// of sleeping for the full on/off duration.
+pub struct BlinkScheduler {
+    pattern: BlinkPattern,
+    step: usize,
+    next_toggle: Instant,
+    led_on: bool,
+}
+
+impl BlinkScheduler {
+    pub fn new(pattern: BlinkPattern) -> Self {
+        let (duration, led_on) = pattern.step(0);
+        Self {
+            pattern,
+            step: 0,
+            next_toggle: Instant::now() + duration,
+            led_on,
+        }
+    }
+
+    /// Replace the pattern at runtime, restarting it from its first step.
+    pub fn set_pattern(&mut self, pattern: BlinkPattern) {
+        let (duration, led_on) = pattern.step(0);
+        self.pattern = pattern;
+        self.step = 0;
+        self.next_toggle = Instant::now() + duration;
+        self.led_on = led_on;
+    }
+
+    /// The current LED state.
+    pub fn led_on(&self) -> bool {
+        self.led_on
+    }
+
+    /// Advance the schedule if the deadline has passed, returning the new LED
+    /// state. Returns `None` if the deadline hasn't passed yet.
+    pub fn poll(&mut self) -> Option<bool> {
+        if Instant::now() < self.next_toggle {
+            return None;
+        }
+
+        self.step += 1;
+        let (duration, led_on) = self.pattern.step(self.step);
+        self.next_toggle = Instant::now() + duration;
+        self.led_on = led_on;
+        Some(led_on)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+    use std::time::Duration as StdDuration;
+
+    #[test]
+    fn new_starts_at_the_pattern_first_step() {
+        let scheduler = BlinkScheduler::new(BlinkPattern::steady(5, 5));
+        assert!(scheduler.led_on());
+    }
+
+    #[test]
+    fn poll_returns_none_before_the_deadline() {
+        let mut scheduler = BlinkScheduler::new(BlinkPattern::steady(50, 50));
+        assert_eq!(scheduler.poll(), None);
+    }
+
+    #[test]
+    fn poll_advances_a_steady_pattern_past_the_deadline() {
+        let mut scheduler = BlinkScheduler::new(BlinkPattern::steady(5, 5));
+        sleep(StdDuration::from_millis(10));
+        assert_eq!(scheduler.poll(), Some(false));
+        sleep(StdDuration::from_millis(10));
+        assert_eq!(scheduler.poll(), Some(true));
+    }
+
+    #[test]
+    fn poll_advances_through_a_multi_element_sequence() {
+        let mut scheduler = BlinkScheduler::new(BlinkPattern::sequence([5, 5, 5]));
+        sleep(StdDuration::from_millis(10));
+        assert_eq!(scheduler.poll(), Some(false));
+        sleep(StdDuration::from_millis(10));
+        assert_eq!(scheduler.poll(), Some(true));
+        sleep(StdDuration::from_millis(10));
+        // Wraps to the first element, which is "on" again for an odd-length sequence.
+        assert_eq!(scheduler.poll(), Some(true));
+    }
+
+    #[test]
+    fn set_pattern_restarts_from_the_first_step() {
+        let mut scheduler = BlinkScheduler::new(BlinkPattern::steady(5, 5));
+        sleep(StdDuration::from_millis(10));
+        scheduler.poll();
+
+        scheduler.set_pattern(BlinkPattern::steady(5, 5));
+        assert!(scheduler.led_on());
+        assert_eq!(scheduler.poll(), None);
+    }
+}
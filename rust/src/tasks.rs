@@ -0,0 +1,86 @@
+use embassy_time::{Duration, Instant, Timer};
+
+use crate::blink_pattern::BlinkPattern;
+use crate::led_strip::{LedStrip, Rgb};
+use crate::net::{self, Mqtt, Telemetry};
+use crate::scheduler::BlinkScheduler;
+use crate::watchdog;
+
+const OFF: Rgb = Rgb::new(0, 0, 0);
+const RED: Rgb = Rgb::new(20, 0, 0);
+
+/// How often the blink loop polls for pending work (toggle deadlines, remote
+/// commands) instead of sleeping for a full on/off duration. This is also the
+/// cadence at which the watchdog gets fed.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+fn pixel_for(led_on: bool) -> Rgb {
+    if led_on {
+        RED
+    } else {
+        OFF
+    }
+}
+
+/// Drives the onboard addressable LED from a [`BlinkPattern`] via a
+/// non-blocking [`BlinkScheduler`]. When `mqtt` is set: a command on the
+/// command topic forces the LED state until a new pattern is selected on the
+/// pattern topic, which hands control back to the scheduler; telemetry is
+/// published periodically and on every LED state change. Feeds the watchdog
+/// every poll.
+#[embassy_executor::task]
+pub async fn blink(mut led: LedStrip<'static>, mut mqtt: Option<Mqtt>) {
+    let mut scheduler = BlinkScheduler::new(BlinkPattern::steady(500, 800));
+    let mut led_on = scheduler.led_on();
+    let mut last_publish = Instant::now() - net::PUBLISH_INTERVAL;
+
+    if let Err(e) = led.write_pixels(&[pixel_for(led_on)]) {
+        log::error!("Failed to write LED pixels: {:?}", e);
+    }
+
+    loop {
+        if let Some(mqtt) = &mqtt {
+            if let Some(spec) = mqtt.take_requested_pattern() {
+                match BlinkPattern::parse(&spec) {
+                    Some(pattern) => scheduler.set_pattern(pattern),
+                    None => log::warn!("Ignoring malformed blink pattern spec: {:?}", spec),
+                }
+            }
+        }
+
+        let previous = led_on;
+        led_on = match mqtt.as_ref().and_then(Mqtt::requested_led_state) {
+            Some(forced) => forced,
+            None => scheduler.poll().unwrap_or(led_on),
+        };
+
+        if led_on != previous {
+            if let Err(e) = led.write_pixels(&[pixel_for(led_on)]) {
+                log::error!("Failed to write LED pixels: {:?}", e);
+            }
+            log::info!("Hehhhhhhhhhhllo, world!");
+        }
+
+        if let Some(mqtt) = &mut mqtt {
+            let state_changed = led_on != previous;
+            let interval_elapsed = last_publish.elapsed() >= net::PUBLISH_INTERVAL;
+            if state_changed || interval_elapsed {
+                let telemetry = Telemetry {
+                    uptime_secs: net::uptime_secs(),
+                    heap_free: net::heap_free(),
+                    led_on,
+                };
+                if let Err(e) = mqtt.publish_telemetry(telemetry) {
+                    log::error!("Failed to publish telemetry: {:?}", e);
+                }
+                last_publish = Instant::now();
+            }
+        }
+
+        Timer::after(POLL_INTERVAL).await;
+
+        if let Err(e) = watchdog::feed() {
+            log::error!("Failed to feed watchdog: {:?}", e);
+        }
+    }
+}
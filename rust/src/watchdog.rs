@@ -0,0 +1,43 @@
+use esp_idf_svc::sys::{
+    esp, esp_task_wdt_add, esp_task_wdt_config_t, esp_task_wdt_delete, esp_task_wdt_deinit,
+    esp_task_wdt_init, esp_task_wdt_reset, EspError,
+};
+
+/// Watchdog timeout in milliseconds. `0` means the watchdog is disabled.
+pub const TIMEOUT_MS: u32 = 3000;
+
+/// Initialize the task watchdog timer and subscribe the calling task to it.
+///
+/// No-op if `timeout_ms` is `0`.
+pub fn enable(timeout_ms: u32) -> Result<(), EspError> {
+    if timeout_ms == 0 {
+        return Ok(());
+    }
+
+    unsafe {
+        let config = esp_task_wdt_config_t {
+            timeout_ms,
+            idle_core_mask: (1 << 0), // Monitor core 0's idle task
+            trigger_panic: true,
+        };
+        esp!(esp_task_wdt_init(&config))?;
+        esp!(esp_task_wdt_add(core::ptr::null_mut()))?;
+    }
+
+    Ok(())
+}
+
+/// Reset the watchdog timer for the calling task. Call this once per main loop
+/// iteration after the real work is done.
+pub fn feed() -> Result<(), EspError> {
+    unsafe { esp!(esp_task_wdt_reset()) }
+}
+
+/// Unsubscribe the calling task and tear down the watchdog timer.
+pub fn disable() -> Result<(), EspError> {
+    unsafe {
+        esp!(esp_task_wdt_delete(core::ptr::null_mut()))?;
+        esp!(esp_task_wdt_deinit())?;
+    }
+    Ok(())
+}